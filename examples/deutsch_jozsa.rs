@@ -91,7 +91,7 @@ fn run_deutsch_jozsa<const N: usize>(
     let mut rng = SmallRng::seed_from_u64(42);
 
     // 5. Run the circuit and measure the state of the *input* qubits.
-    sim.run();
+    sim.run(&mut rng);
     let full_measurement = sim.measure(&mut rng);
 
     // The result is determined by the state of the first N qubits.
@@ -47,7 +47,7 @@ fn build_diffuser(n_qubits: u8) -> Result<Circuit, QubitError> {
     for i in 0..n_qubits {
         diffuser_circuit.add_gate(Gate::hadamard(), [i])?;
     }
-    diffuser_circuit.add_gate(zero_oracle.into_gate(), all_qubits.clone())?;
+    diffuser_circuit.add_gate(zero_oracle.into_gate()?, all_qubits.clone())?;
     for i in 0..n_qubits {
         diffuser_circuit.add_gate(Gate::hadamard(), [i])?;
     }
@@ -72,6 +72,10 @@ fn main() -> Result<(), QubitError> {
     let mut sim = QuantumSimulator::new(N_QUBITS);
     let all_qubits: Vec<u8> = (0..N_QUBITS).collect();
 
+    // Create a seeded RNG for reproducible measurements.
+    // For a real simulation, you might seed this from the system time.
+    let mut rng = SmallRng::seed_from_u64(42);
+
     // Build the main algorithm components once.
     let oracle = build_oracle(N_QUBITS, WINNING_STATE)?;
     let diffuser = build_diffuser(N_QUBITS)?;
@@ -81,21 +85,21 @@ fn main() -> Result<(), QubitError> {
         sim.add_gate(Gate::hadamard(), [i])?;
     }
     sim.init_state(0);
-    sim.run(); // Applies the Hadamard gates.
+    sim.run(&mut rng); // Applies the Hadamard gates.
     println!("\n--- 1. State after Initial Superposition ---");
     println!("{}", sim.state());
 
     // Step 2: Apply the Oracle.
-    sim.add_gate(oracle.clone().into_gate(), all_qubits.clone())?;
+    sim.add_gate(oracle.clone().into_gate()?, all_qubits.clone())?;
     sim.init_state(0);
-    sim.run();
+    sim.run(&mut rng);
     println!("\n--- 2. State after Oracle (amplitude of |101> is flipped) ---");
     println!("{}", sim.state());
 
     // Step 3: Apply the Diffuser.
-    sim.add_gate(diffuser.clone().into_gate(), all_qubits.clone())?;
+    sim.add_gate(diffuser.clone().into_gate()?, all_qubits.clone())?;
     sim.init_state(0);
-    sim.run();
+    sim.run(&mut rng);
     println!("\n--- 3. State after 1st Grover Iteration (amplitude of |101> is amplified) ---");
     println!("{}", sim.state());
 
@@ -105,14 +109,14 @@ fn main() -> Result<(), QubitError> {
     // Add the remaining Grover iterations to the circuit.
     if num_iterations > 1 {
         for _ in 1..num_iterations {
-            sim.add_gate(oracle.clone().into_gate(), all_qubits.clone())?;
-            sim.add_gate(diffuser.clone().into_gate(), all_qubits.clone())?;
+            sim.add_gate(oracle.clone().into_gate()?, all_qubits.clone())?;
+            sim.add_gate(diffuser.clone().into_gate()?, all_qubits.clone())?;
         }
     }
 
     // Run the complete circuit from the start.
     sim.init_state(0);
-    sim.run();
+    sim.run(&mut rng);
     println!("\n--- State after {num_iterations} Grover Iterations ---");
     println!("{}", sim.state());
 
@@ -120,9 +124,6 @@ fn main() -> Result<(), QubitError> {
     println!("\n=== Part 3: Statistical Analysis ===");
     println!("Running simulation 1000 times to find success rate...");
 
-    // For a real simulation, you might seed this from the system time.
-    let mut rng = SmallRng::seed_from_u64(42);
-
     let mut success_count = 0;
     let n_runs = 1000;
 
@@ -130,7 +131,7 @@ fn main() -> Result<(), QubitError> {
         // The simulator `sim` now contains the complete, optimal circuit.
         // We just need to re-initialize and re-run for each trial.
         sim.init_state(0);
-        sim.run();
+        sim.run(&mut rng);
         let outcome = sim.measure(&mut rng);
 
         if outcome == WINNING_STATE {
@@ -16,7 +16,7 @@ fn main() -> Result<(), QubitError> {
 
     // 1. Build the QFT circuit for 3 qubits.
     // We can then treat this entire circuit as a single, reusable gate.
-    let qft_gate = qft::build_qft_circuit(NUM_QUBITS)?.into_gate();
+    let qft_gate = qft::build_qft_circuit(NUM_QUBITS)?.into_gate()?;
 
     // 2. Initialize the simulator and add the QFT gate.
     let mut sim = QuantumSimulator::new(NUM_QUBITS);
@@ -32,15 +32,15 @@ fn main() -> Result<(), QubitError> {
         width = NUM_QUBITS as usize
     );
 
-    // 4. Run the simulation once to see the final state vector.
-    sim.run();
-    println!("\nState vector after applying QFT:");
-    println!("{}", sim.state());
-
-    // 5. Create a seeded RNG for reproducible measurements.
+    // 4. Create a seeded RNG for reproducible measurements.
     // For a real simulation, you might seed this from the system time.
     let mut rng = SmallRng::seed_from_u64(42);
 
+    // 5. Run the simulation once to see the final state vector.
+    sim.run(&mut rng);
+    println!("\nState vector after applying QFT:");
+    println!("{}", sim.state());
+
     // 6. Run the simulation many times to see the probability distribution.
     // The QFT of a basis state results in a uniform superposition, so all
     // outcomes should be roughly equally likely.
@@ -51,7 +51,7 @@ fn main() -> Result<(), QubitError> {
     for _ in 0..n_iterations {
         // Reset the state and run the simulation for each measurement.
         sim.init_state(initial_state_index);
-        sim.run();
+        sim.run(&mut rng);
         let outcome = sim.measure(&mut rng);
         histogram[outcome] += 1;
     }
@@ -7,14 +7,14 @@
 
 use core::{error, fmt};
 
-use alloc::{vec, vec::Vec};
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
 
 use num_complex::Complex64;
 
-use super::gate::Gate;
+use super::{gate::Gate, noise::NoiseChannel};
 
 // The state size is 2^number of qubits, so limit the number to protect from memory overflow
-const MAX_QUBITS: u8 = 32;
+pub(crate) const MAX_QUBITS: u8 = 32;
 
 /// Represents errors that can occur during circuit construction
 #[derive(Debug)]
@@ -23,6 +23,14 @@ pub enum QubitError {
     IndexOutOfBounds,
     /// Indicates that the same qubit index was used multiple times for a single gate
     DuplicatedIndex,
+    /// Indicates that an OpenQASM source string could not be parsed
+    InvalidQasm,
+    /// Indicates that an amplitude vector had the wrong length or wasn't normalized
+    InvalidAmplitudes,
+    /// Indicates that a circuit can't be used as a `Gate` kernel: either it
+    /// contains `Measure`/`Reset`/`IfGate` steps, or it has no fixed qubit
+    /// count of its own (e.g. one built via [`Circuit::from_gate`])
+    ImpureCircuit,
 }
 
 /// A trait for types that can be converted into an iterator over qubit indices
@@ -31,6 +39,18 @@ impl fmt::Display for QubitError {
         match self {
             QubitError::IndexOutOfBounds => write!(f, "Index is out of bounds"),
             QubitError::DuplicatedIndex => write!(f, "Duplicated index"),
+            QubitError::InvalidQasm => write!(f, "Invalid or unsupported OpenQASM source"),
+            QubitError::InvalidAmplitudes => {
+                write!(
+                    f,
+                    "Amplitude vector has the wrong length or isn't normalized"
+                )
+            }
+            QubitError::ImpureCircuit => write!(
+                f,
+                "Circuit contains Measure/Reset/IfGate steps, or has no fixed qubit count, \
+                 and can't be used as a Gate kernel"
+            ),
         }
     }
 }
@@ -61,7 +81,7 @@ where
 /// Gate with a map between its qubit local state indices and full state indices
 #[derive(Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
-struct MappedGate {
+pub(crate) struct MappedGate {
     /// Gate kernel
     gate: Gate,
 
@@ -141,7 +161,53 @@ impl MappedGate {
         })
     }
 
-    fn apply(&self, state: &mut [Complex64]) {
+    /// Returns the gate kernel this `MappedGate` wraps
+    pub(crate) fn gate(&self) -> &Gate {
+        &self.gate
+    }
+
+    /// Recovers the original `[control_1..control_n, target...]` qubit indices
+    /// passed to `Circuit::add_gate`, by inverting `calc_local_bit_pos`.
+    pub(crate) fn original_qubits(&self) -> Vec<u8> {
+        let qubit_count = self.qubits_mask.count_ones() as usize;
+        let n_controlled = self.gate.count_controlled() as usize;
+
+        (0..qubit_count)
+            .map(|input_index| {
+                let local_bit_pos =
+                    Self::calc_local_bit_pos(input_index, qubit_count, n_controlled);
+                // Exactly one qubit's bit was deposited at `local_bit_pos`, so the
+                // state_map entry for the singleton local index isolates its mask.
+                let qubit_mask = self.state_map[1 << local_bit_pos];
+                qubit_mask.trailing_zeros() as u8
+            })
+            .collect()
+    }
+
+    /// Returns a `MappedGate` applying the inverse kernel over the same qubit mapping
+    fn inverted(&self) -> Self {
+        Self {
+            gate: self.gate.inverse(),
+            state_map: self.state_map.clone(),
+            qubits_mask: self.qubits_mask,
+        }
+    }
+
+    pub(crate) fn apply(&self, state: &mut [Complex64]) {
+        #[cfg(feature = "rayon")]
+        {
+            // Parallelizing needs more than one substate block; a gate that
+            // spans every qubit has a single block, so it takes the
+            // sequential path below and skips rayon's dispatch overhead.
+            if state.len() / self.state_map.len() > 1 {
+                return self.apply_parallel(state);
+            }
+        }
+
+        self.apply_sequential(state);
+    }
+
+    fn apply_sequential(&self, state: &mut [Complex64]) {
         let mut substate = vec![Complex64::ZERO; self.state_map.len()];
         let mut outer_index = 0;
 
@@ -160,14 +226,98 @@ impl MappedGate {
             outer_index = ((outer_index | self.qubits_mask) + 1) & !self.qubits_mask;
         }
     }
+
+    // Parallel counterpart of `apply_sequential`, gated behind the `rayon`
+    // feature (which pulls in `std`, so it's off by default for `no_std` use).
+    //
+    // The sequential path's `outer_index` recurrence visits blocks in order,
+    // each depending on the previous. To process block `b` independently, we
+    // instead deposit the bits of `b` into the zero-positions of `qubits_mask`
+    // (a software parallel-bit-deposit/pdep) to compute that block's
+    // `outer_index` directly. Gather-and-apply for each block is then a pure
+    // function of `b`, safe to run concurrently; only the final scatter back
+    // into `state` needs to happen from one thread at a time, which is cheap.
+    #[cfg(feature = "rayon")]
+    fn apply_parallel(&self, state: &mut [Complex64]) {
+        use rayon::prelude::*;
+
+        let free_mask = !self.qubits_mask & (state.len() - 1);
+        let n_blocks = state.len() / self.state_map.len();
+        let state_ref: &[Complex64] = state;
+
+        let blocks: Vec<(usize, Vec<Complex64>)> = (0..n_blocks)
+            .into_par_iter()
+            .map(|b| {
+                let outer_index = deposit_bits(b, free_mask);
+                let mut substate = vec![Complex64::ZERO; self.state_map.len()];
+
+                for (i, inner_index) in self.state_map.iter().enumerate() {
+                    substate[i] = state_ref[outer_index | inner_index];
+                }
+
+                self.gate.apply(&mut substate);
+
+                (outer_index, substate)
+            })
+            .collect();
+
+        for (outer_index, substate) in blocks {
+            for (i, inner_index) in self.state_map.iter().enumerate() {
+                state[outer_index | inner_index] = substate[i];
+            }
+        }
+    }
+}
+
+// Software parallel-bit-deposit: scatters the bits of `value` into the
+// set-bit positions of `mask`, from LSB to MSB, leaving all other bits zero.
+#[cfg(feature = "rayon")]
+fn deposit_bits(mut value: usize, mask: usize) -> usize {
+    let mut result = 0;
+    let mut remaining_mask = mask;
+
+    while remaining_mask != 0 {
+        let lowest_bit = remaining_mask & remaining_mask.wrapping_neg();
+        if value & 1 != 0 {
+            result |= lowest_bit;
+        }
+        value >>= 1;
+        remaining_mask &= !lowest_bit;
+    }
+
+    result
+}
+
+/// A single step in a `Circuit`'s operation list.
+///
+/// Most circuits are pure gate sequences (`Gate`), but mid-circuit measurement
+/// (`Measure`), qubit reinitialization (`Reset`), and classically-conditioned
+/// gates (`IfGate`) let a circuit's later steps depend on earlier measurement
+/// outcomes, mirroring q1tsim's `CircuitOp` set.
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub(crate) enum CircuitOp {
+    /// Apply a gate to the state vector
+    Gate(MappedGate),
+    /// Collapse `qubit` and record the 0/1 outcome into classical bit `cbit`
+    Measure { qubit: u8, cbit: usize },
+    /// Force `qubit` back to the |0⟩ state
+    Reset { qubit: u8 },
+    /// Apply `gate` only if classical bit `cbit` holds `value`
+    IfGate {
+        cbit: usize,
+        value: bool,
+        gate: MappedGate,
+    },
 }
 
-/// Represents a quantum circuit as a sequence of gates
+/// Represents a quantum circuit as a sequence of operations
 #[derive(Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct Circuit {
     qubit_count: Option<u8>,
-    gates: Vec<MappedGate>,
+    ops: Vec<CircuitOp>,
+    noise: BTreeMap<u8, NoiseChannel>,
 }
 
 impl Circuit {
@@ -179,7 +329,8 @@ impl Circuit {
         );
         Self {
             qubit_count: Some(qubit_count),
-            gates: Vec::new(),
+            ops: Vec::new(),
+            noise: BTreeMap::new(),
         }
     }
 
@@ -192,55 +343,261 @@ impl Circuit {
         gate: Gate,
         qubit_indices: I,
     ) -> Result<(), QubitError> {
-        self.gates
-            .push(MappedGate::new(gate, qubit_indices, self.qubit_count)?);
+        self.ops.push(CircuitOp::Gate(MappedGate::new(
+            gate,
+            qubit_indices,
+            self.qubit_count,
+        )?));
+
+        Ok(())
+    }
+
+    /// Adds a mid-circuit measurement: collapses `qubit` and stores the 0/1
+    /// outcome into classical bit `cbit`.
+    pub fn add_measure(&mut self, qubit: u8, cbit: usize) -> Result<(), QubitError> {
+        self.check_qubit(qubit)?;
+        self.ops.push(CircuitOp::Measure { qubit, cbit });
+        Ok(())
+    }
 
+    /// Adds a reset: forces `qubit` back to the |0⟩ state.
+    pub fn add_reset(&mut self, qubit: u8) -> Result<(), QubitError> {
+        self.check_qubit(qubit)?;
+        self.ops.push(CircuitOp::Reset { qubit });
         Ok(())
     }
 
+    /// Adds a classically-conditioned gate: `gate` is applied only if
+    /// classical bit `cbit` was measured as `value`.
+    pub fn add_conditional_gate<I: QubitIndices>(
+        &mut self,
+        cbit: usize,
+        value: bool,
+        gate: Gate,
+        qubit_indices: I,
+    ) -> Result<(), QubitError> {
+        self.ops.push(CircuitOp::IfGate {
+            cbit,
+            value,
+            gate: MappedGate::new(gate, qubit_indices, self.qubit_count)?,
+        });
+        Ok(())
+    }
+
+    fn check_qubit(&self, qubit: u8) -> Result<(), QubitError> {
+        match self.qubit_count {
+            Some(qubit_count) if qubit >= qubit_count => Err(QubitError::IndexOutOfBounds),
+            _ => Ok(()),
+        }
+    }
+
+    /// Attaches a stochastic noise channel to `qubit`, replacing any channel
+    /// already attached to it.
+    ///
+    /// [`QuantumSimulator::run_noisy`](super::simulator::QuantumSimulator::run_noisy)
+    /// samples and applies this channel after every gate that touches `qubit`.
+    pub fn add_noise(&mut self, qubit: u8, channel: NoiseChannel) -> Result<(), QubitError> {
+        self.check_qubit(qubit)?;
+        self.noise.insert(qubit, channel);
+        Ok(())
+    }
+
+    /// Returns the noise channel attached to `qubit`, if any.
+    pub(crate) fn noise_for_qubit(&self, qubit: u8) -> Option<&NoiseChannel> {
+        self.noise.get(&qubit)
+    }
+
     /// Creates a circuit from a single gate and its target qubits.
     ///
     /// This is useful for treating a single gate operation as a circuit.
     pub fn from_gate<I: QubitIndices>(gate: Gate, qubit_indices: I) -> Result<Self, QubitError> {
         Ok(Self {
             qubit_count: None,
-            gates: vec![MappedGate::new(gate, qubit_indices, None)?],
+            ops: vec![CircuitOp::Gate(MappedGate::new(gate, qubit_indices, None)?)],
+            noise: BTreeMap::new(),
         })
     }
 
+    // Whether every step in this circuit is a plain `Gate`, i.e. it has no
+    // `Measure`/`Reset`/`IfGate` steps that would have no meaning as part of a
+    // unitary kernel.
+    pub(crate) fn is_pure_gate(&self) -> bool {
+        self.ops.iter().all(|op| matches!(op, CircuitOp::Gate(_)))
+    }
+
     /// Consumes the circuit and converts it into a single, composite `Gate`.
     ///
     /// This allows for hierarchical circuits, where a complex circuit can be used
     /// as a single gate within another, larger circuit.
-    pub fn into_gate(self) -> Gate {
+    ///
+    /// Only supported for circuits made entirely of `Gate` operations with a
+    /// fixed qubit count of their own: returns [`QubitError::ImpureCircuit`]
+    /// if this circuit has any `Measure`/`Reset`/`IfGate` step, since those
+    /// have no meaning as part of a unitary kernel, or if it was built via
+    /// [`Circuit::from_gate`], which has no fixed qubit count.
+    pub fn into_gate(self) -> Result<Gate, QubitError> {
         Gate::circuit(self)
     }
 
     /// Creates a new circuit that is the inverse (adjoint) of this circuit
+    ///
+    /// Only the `Gate` and `IfGate` steps are invertible; `Measure` and
+    /// `Reset` steps are not unitary and are carried over unchanged.
     pub fn inverse(&self) -> Self {
-        let inverted_gates = self
-            .gates
+        let inverted_ops = self
+            .ops
             .iter()
-            // Reverse the order of the gates
+            // Reverse the order of the operations
             .rev()
-            // Take the inverse of each gate (the mapping to qubits remains the same)
-            .map(|mapped_gate| MappedGate {
-                gate: mapped_gate.gate.inverse(),
-                state_map: mapped_gate.state_map.clone(),
-                qubits_mask: mapped_gate.qubits_mask,
+            .map(|op| match op {
+                CircuitOp::Gate(mapped_gate) => CircuitOp::Gate(mapped_gate.inverted()),
+                CircuitOp::IfGate { cbit, value, gate } => CircuitOp::IfGate {
+                    cbit: *cbit,
+                    value: *value,
+                    gate: gate.inverted(),
+                },
+                op => op.clone(),
             })
             .collect();
 
         Self {
             qubit_count: self.qubit_count,
-            gates: inverted_gates,
+            ops: inverted_ops,
+            noise: self.noise.clone(),
         }
     }
 
     /// Applies the entire sequence of gates in the circuit to the given state vector.
+    ///
+    /// Only supported for circuits made entirely of `Gate` operations; use
+    /// [`QuantumSimulator::run`](super::simulator::QuantumSimulator::run) to
+    /// execute circuits containing measurement or classically-conditioned steps.
     pub fn apply(&self, state: &mut [Complex64]) {
-        for gate in &self.gates {
-            gate.apply(state)
+        assert!(
+            self.is_pure_gate(),
+            "Circuit::apply only supports pure-gate circuits; use QuantumSimulator::run for Measure/Reset/IfGate"
+        );
+
+        for op in &self.ops {
+            match op {
+                CircuitOp::Gate(gate) => gate.apply(state),
+                CircuitOp::Measure { .. } | CircuitOp::Reset { .. } | CircuitOp::IfGate { .. } => {
+                    unreachable!("checked by the is_pure_gate assertion above")
+                }
+            }
         }
     }
+
+    /// Returns the number of qubits this circuit was built for, if known.
+    ///
+    /// Circuits created with `from_gate` have no fixed qubit count of their own.
+    pub(crate) fn qubit_count(&self) -> Option<u8> {
+        self.qubit_count
+    }
+
+    /// Returns one past the highest classical bit index referenced by any
+    /// `Measure` or `IfGate` operation in this circuit.
+    pub(crate) fn classical_bit_count(&self) -> usize {
+        self.ops
+            .iter()
+            .filter_map(|op| match op {
+                CircuitOp::Measure { cbit, .. } | CircuitOp::IfGate { cbit, .. } => Some(cbit + 1),
+                CircuitOp::Gate(_) | CircuitOp::Reset { .. } => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the circuit's operations, in application order
+    pub(crate) fn ops(&self) -> &[CircuitOp] {
+        &self.ops
+    }
+
+    /// Returns the circuit's gate-only operations, in application order,
+    /// skipping `Measure`/`Reset`/`IfGate` steps.
+    pub(crate) fn gate_ops(&self) -> impl Iterator<Item = &MappedGate> {
+        self.ops.iter().filter_map(|op| match op {
+            CircuitOp::Gate(gate) => Some(gate),
+            _ => None,
+        })
+    }
+
+    /// Serializes this circuit to OpenQASM 2.0 source text.
+    ///
+    /// See the [`qasm`](super::qasm) module for the supported gate subset.
+    pub fn to_qasm(&self) -> String {
+        super::qasm::to_qasm(self)
+    }
+
+    /// Parses OpenQASM 2.0 source text into a `Circuit`.
+    ///
+    /// See the [`qasm`](super::qasm) module for the supported gate subset.
+    pub fn from_qasm(src: &str) -> Result<Self, QubitError> {
+        super::qasm::from_qasm(src)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use crate::{simulator::Basis, tests::assert_state_eq, Gate, QuantumSimulator};
+
+    use super::MappedGate;
+
+    // Standard quantum teleportation: qubit 0 carries the message, the
+    // entangled pair is (1, 2), and the corrections on qubit 2 are applied
+    // via `IfGate` steps keyed off the `Measure`d classical bits. This only
+    // passes if `Measure`/`IfGate` steps are actually collapsed and applied
+    // by `run`, rather than silently skipped.
+    #[test]
+    fn teleportation_reproduces_message_qubit() {
+        let mut sim = QuantumSimulator::new(3);
+        sim.add_gate(Gate::not(), [0]).unwrap(); // Prepare message = |1>.
+        sim.add_gate(Gate::hadamard(), [1]).unwrap();
+        sim.add_gate(Gate::cnot(), [1, 2]).unwrap();
+        sim.add_gate(Gate::cnot(), [0, 1]).unwrap();
+        sim.add_gate(Gate::hadamard(), [0]).unwrap();
+        sim.add_measure(0, 0).unwrap();
+        sim.add_measure(1, 1).unwrap();
+        sim.add_conditional_gate(1, true, Gate::not(), [2]).unwrap();
+        sim.add_conditional_gate(0, true, Gate::z(), [2]).unwrap();
+
+        sim.init_state(0);
+        let mut rng = SmallRng::seed_from_u64(0);
+        sim.run(&mut rng);
+
+        // Regardless of the (random) Bell-measurement outcome, qubit 2 always
+        // ends up in the message's original |1> state once the corrections
+        // are applied. `peek_prob` reports P(qubit == 0), so that's 0 here.
+        assert_eq!(sim.peek_prob(2, Basis::Z), 0.0);
+    }
+
+    // `apply_parallel` recomputes each block's `outer_index` independently via
+    // `deposit_bits` instead of the sequential recurrence, so it needs its own
+    // coverage: a gate spanning non-adjacent qubits, applied to a state with
+    // several blocks' worth of untouched qubits, should land on exactly the
+    // same result either way.
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn apply_parallel_matches_apply_sequential() {
+        use num_complex::Complex64;
+
+        let gate = Gate::phase_fraction(0.25).control();
+        let mapped = MappedGate::new(gate, [1u8, 3u8], None).unwrap();
+
+        let initial_state: Vec<Complex64> = (0..16)
+            .map(|i| Complex64::new(i as f64, -(i as f64)))
+            .collect();
+
+        let mut sequential_state = initial_state.clone();
+        mapped.apply_sequential(&mut sequential_state);
+
+        let mut parallel_state = initial_state;
+        mapped.apply_parallel(&mut parallel_state);
+
+        assert_state_eq(&parallel_state, &sequential_state);
+    }
 }
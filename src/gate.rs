@@ -4,13 +4,13 @@
 //! Gates can be basic (like Hadamard or NOT), parameterized (Phase), or composite
 //! (Controlled gates or entire Circuits treated as a single gate).
 
-use core::f64::consts::{FRAC_1_SQRT_2, PI};
+use core::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_4, PI};
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec, vec::Vec};
 
 use num_complex::Complex64;
 
-use super::circuit::Circuit;
+use super::circuit::{Circuit, QubitError};
 
 /// Represents different types of quantum gates
 #[derive(Debug, Clone)]
@@ -28,6 +28,40 @@ pub enum Gate {
     Controlled(Box<Gate>),
     /// Nested circuit as a gate
     Circuit(Circuit),
+    /// A classical reversible function, as a permutation of basis-state indices
+    Oracle(Vec<usize>),
+    /// Pauli-Y gate - bit and phase flip
+    Y,
+    /// Pauli-Z gate - phase flip
+    Z,
+    /// S gate - quarter-turn phase gate (phase π/2)
+    S,
+    /// S† gate - the inverse of `S` (phase −π/2)
+    Sdg,
+    /// T gate - eighth-turn phase gate (phase π/4)
+    T,
+    /// T† gate - the inverse of `T` (phase −π/4)
+    Tdg,
+    /// Rotation by angle θ (radians) about the X axis
+    Rx(f64),
+    /// Rotation by angle θ (radians) about the Y axis
+    Ry(f64),
+    /// Rotation by angle θ (radians) about the Z axis
+    Rz(f64),
+}
+
+// Checks that `perm` is a bijection on `0..perm.len()`.
+fn is_permutation(perm: &[usize]) -> bool {
+    let mut seen = vec![false; perm.len()];
+
+    for &p in perm {
+        if p >= perm.len() || seen[p] {
+            return false;
+        }
+        seen[p] = true;
+    }
+
+    true
 }
 
 impl Gate {
@@ -60,6 +94,60 @@ impl Gate {
         Self::Swap
     }
 
+    /// Creates a Pauli-Y gate
+    #[inline]
+    pub fn y() -> Self {
+        Self::Y
+    }
+
+    /// Creates a Pauli-Z gate
+    #[inline]
+    pub fn z() -> Self {
+        Self::Z
+    }
+
+    /// Creates an S gate (phase π/2)
+    #[inline]
+    pub fn s() -> Self {
+        Self::S
+    }
+
+    /// Creates an S† gate (phase −π/2)
+    #[inline]
+    pub fn sdg() -> Self {
+        Self::Sdg
+    }
+
+    /// Creates a T gate (phase π/4)
+    #[inline]
+    pub fn t() -> Self {
+        Self::T
+    }
+
+    /// Creates a T† gate (phase −π/4)
+    #[inline]
+    pub fn tdg() -> Self {
+        Self::Tdg
+    }
+
+    /// Creates a rotation by `theta` radians about the X axis
+    #[inline]
+    pub fn rx(theta: f64) -> Self {
+        Self::Rx(theta)
+    }
+
+    /// Creates a rotation by `theta` radians about the Y axis
+    #[inline]
+    pub fn ry(theta: f64) -> Self {
+        Self::Ry(theta)
+    }
+
+    /// Creates a rotation by `theta` radians about the Z axis
+    #[inline]
+    pub fn rz(theta: f64) -> Self {
+        Self::Rz(theta)
+    }
+
     /// Creates a controlled version of the given gate
     #[inline]
     pub fn control(self) -> Self {
@@ -71,10 +159,33 @@ impl Gate {
         (0..n).fold(self, |gate, _| gate.control())
     }
 
-    /// Creates a gate from a circuit
+    /// Creates a gate from a circuit.
+    ///
+    /// Returns [`QubitError::ImpureCircuit`] if `circuit` has any
+    /// `Measure`/`Reset`/`IfGate` step, since those have no meaning as part of
+    /// a unitary kernel, or if `circuit` has no fixed qubit count of its own
+    /// (e.g. one built via [`Circuit::from_gate`]), since a nested `Gate`
+    /// needs to know its own arity.
+    #[inline]
+    pub fn circuit(circuit: Circuit) -> Result<Self, QubitError> {
+        if !circuit.is_pure_gate() || circuit.qubit_count().is_none() {
+            return Err(QubitError::ImpureCircuit);
+        }
+        Ok(Self::Circuit(circuit))
+    }
+
+    /// Creates a gate implementing a classical reversible function as a
+    /// permutation of basis-state indices.
+    ///
+    /// `perm[i]` is the index that basis state `i` is sent to; `perm` must be
+    /// a bijection on `0..perm.len()`, which debug builds assert.
     #[inline]
-    pub fn circuit(circuit: Circuit) -> Self {
-        Self::Circuit(circuit)
+    pub fn permutation(perm: Vec<usize>) -> Self {
+        debug_assert!(
+            is_permutation(&perm),
+            "Oracle gate requires a bijection on 0..perm.len()"
+        );
+        Self::Oracle(perm)
     }
 
     /// Creates a CNOT gate (controlled NOT)
@@ -105,6 +216,38 @@ impl Gate {
         }
     }
 
+    // Returns the number of qubits this gate acts on as a standalone unit,
+    // i.e. the length of the qubit index list a caller must pass to
+    // `Circuit::add_gate`/`Circuit::from_gate` for it.
+    pub(crate) fn qubit_count(&self) -> u8 {
+        match self {
+            Self::Hadamard
+            | Self::Not
+            | Self::Phase(_)
+            | Self::Y
+            | Self::Z
+            | Self::S
+            | Self::Sdg
+            | Self::T
+            | Self::Tdg
+            | Self::Rx(_)
+            | Self::Ry(_)
+            | Self::Rz(_) => 1,
+            Self::Swap => 2,
+            Self::Controlled(inner) => inner.qubit_count() + 1,
+            Self::Circuit(circuit) => circuit.qubit_count().expect(
+                "Gate::circuit rejects circuits with no fixed qubit count, so this is always Some",
+            ),
+            Self::Oracle(perm) => {
+                debug_assert!(
+                    perm.len().is_power_of_two(),
+                    "Oracle gate's permutation length must be a power of two"
+                );
+                perm.len().ilog2() as u8
+            }
+        }
+    }
+
     /// Returns the inverse (adjoint) of this gate
     #[inline]
     pub fn inverse(&self) -> Self {
@@ -120,6 +263,30 @@ impl Gate {
             // Recursively find the inverse of composite gates
             Self::Controlled(inner_gate) => Self::Controlled(Box::new(inner_gate.inverse())),
             Self::Circuit(circuit) => Self::Circuit(circuit.inverse()),
+
+            // The inverse of a permutation is the permutation that undoes it.
+            Self::Oracle(perm) => {
+                let mut inverse = vec![0; perm.len()];
+                for (i, &p) in perm.iter().enumerate() {
+                    inverse[p] = i;
+                }
+                Self::Oracle(inverse)
+            }
+
+            // Y and Z are self-adjoint.
+            Self::Y => Self::Y,
+            Self::Z => Self::Z,
+
+            // S and T are inverses of their dagger counterparts.
+            Self::S => Self::Sdg,
+            Self::Sdg => Self::S,
+            Self::T => Self::Tdg,
+            Self::Tdg => Self::T,
+
+            // Rotating by the negated angle undoes a rotation.
+            Self::Rx(theta) => Self::Rx(-theta),
+            Self::Ry(theta) => Self::Ry(-theta),
+            Self::Rz(theta) => Self::Rz(-theta),
         }
     }
 
@@ -159,6 +326,62 @@ impl Gate {
             Self::Circuit(circuit) => {
                 circuit.apply(state);
             }
+            Self::Oracle(perm) => {
+                debug_assert_eq!(
+                    state.len(),
+                    perm.len(),
+                    "Oracle gate requires a substate the same length as its permutation"
+                );
+                let original = state.to_vec();
+                for (i, &p) in perm.iter().enumerate() {
+                    state[p] = original[i];
+                }
+            }
+            Self::Y => {
+                debug_assert!(state.len() >= 2, "Y gate requires at least 2 amplitudes");
+                let (a, b) = (state[0], state[1]);
+                state[0] = Complex64::new(0.0, -1.0) * b;
+                state[1] = Complex64::new(0.0, 1.0) * a;
+            }
+            Self::Z => {
+                debug_assert!(state.len() >= 2, "Z gate requires at least 2 amplitudes");
+                state[1] = -state[1];
+            }
+            Self::S => {
+                debug_assert!(state.len() >= 2, "S gate requires at least 2 amplitudes");
+                state[1] *= Complex64::new(0.0, 1.0);
+            }
+            Self::Sdg => {
+                debug_assert!(state.len() >= 2, "Sdg gate requires at least 2 amplitudes");
+                state[1] *= Complex64::new(0.0, -1.0);
+            }
+            Self::T => {
+                debug_assert!(state.len() >= 2, "T gate requires at least 2 amplitudes");
+                state[1] *= Complex64::new(0.0, FRAC_PI_4).exp();
+            }
+            Self::Tdg => {
+                debug_assert!(state.len() >= 2, "Tdg gate requires at least 2 amplitudes");
+                state[1] *= Complex64::new(0.0, -FRAC_PI_4).exp();
+            }
+            Self::Rx(theta) => {
+                debug_assert!(state.len() >= 2, "Rx gate requires at least 2 amplitudes");
+                let (cos, sin) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+                let (a, b) = (state[0], state[1]);
+                state[0] = cos * a - Complex64::new(0.0, sin) * b;
+                state[1] = -Complex64::new(0.0, sin) * a + cos * b;
+            }
+            Self::Ry(theta) => {
+                debug_assert!(state.len() >= 2, "Ry gate requires at least 2 amplitudes");
+                let (cos, sin) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+                let (a, b) = (state[0], state[1]);
+                state[0] = cos * a - sin * b;
+                state[1] = sin * a + cos * b;
+            }
+            Self::Rz(theta) => {
+                debug_assert!(state.len() >= 2, "Rz gate requires at least 2 amplitudes");
+                state[0] *= Complex64::new(0.0, -theta / 2.0).exp();
+                state[1] *= Complex64::new(0.0, theta / 2.0).exp();
+            }
         }
     }
 }
@@ -259,4 +482,124 @@ pub(crate) mod tests {
     fn fredkin_gate() {
         run_gate_tests("FREDKIN", Gate::fredkin(), &*vectors::FREDKIN_TESTS);
     }
+
+    // The Oracle gate isn't covered by the `vectors` test data (it has no
+    // fixed matrix to compare against Qiskit), so it's exercised directly
+    // against a hand-built permutation instead.
+    #[test]
+    fn oracle_gate_permutes_and_inverts() {
+        let perm = vec![2usize, 0, 3, 1];
+        let gate = Gate::permutation(perm);
+
+        let initial_state: Vec<Complex64> = (0..4).map(|i| Complex64::new(i as f64, 0.0)).collect();
+        let mut state = initial_state.clone();
+
+        gate.apply(&mut state);
+        assert_state_eq(
+            &state,
+            &[
+                Complex64::new(1.0, 0.0),
+                Complex64::new(3.0, 0.0),
+                Complex64::new(0.0, 0.0),
+                Complex64::new(2.0, 0.0),
+            ],
+        );
+
+        gate.inverse().apply(&mut state);
+        assert_state_eq(&state, &initial_state);
+    }
+
+    // Like the Oracle gate, these new single-qubit gates aren't covered by
+    // the `vectors` test data, so they're checked directly against their
+    // hand-computed 2x2 matrices instead.
+    #[test]
+    fn y_gate() {
+        let mut state = [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)];
+        Gate::y().apply(&mut state);
+        assert_state_eq(
+            &state,
+            &[Complex64::new(0.0, 0.0), Complex64::new(0.0, 1.0)],
+        );
+    }
+
+    #[test]
+    fn z_gate() {
+        let mut state = [Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0)];
+        Gate::z().apply(&mut state);
+        assert_state_eq(
+            &state,
+            &[Complex64::new(0.0, 0.0), Complex64::new(-1.0, 0.0)],
+        );
+    }
+
+    #[test]
+    fn s_and_sdg_gates_invert() {
+        let initial_state = [
+            Complex64::new(FRAC_1_SQRT_2, 0.0),
+            Complex64::new(FRAC_1_SQRT_2, 0.0),
+        ];
+        let mut state = initial_state;
+        Gate::s().apply(&mut state);
+        assert_state_eq(
+            &state,
+            &[
+                Complex64::new(FRAC_1_SQRT_2, 0.0),
+                Complex64::new(0.0, FRAC_1_SQRT_2),
+            ],
+        );
+        Gate::s().inverse().apply(&mut state);
+        assert_state_eq(&state, &initial_state);
+        assert_eq!(Gate::s().inverse(), Gate::sdg());
+    }
+
+    #[test]
+    fn t_and_tdg_gates_invert() {
+        let initial_state = [
+            Complex64::new(FRAC_1_SQRT_2, 0.0),
+            Complex64::new(FRAC_1_SQRT_2, 0.0),
+        ];
+        let mut state = initial_state;
+        Gate::t().apply(&mut state);
+        Gate::t().inverse().apply(&mut state);
+        assert_state_eq(&state, &initial_state);
+        assert_eq!(Gate::t().inverse(), Gate::tdg());
+    }
+
+    #[test]
+    fn rx_gate_full_turn_is_identity_up_to_global_phase() {
+        let initial_state = [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)];
+        let mut state = initial_state;
+        Gate::rx(2.0 * PI).apply(&mut state);
+        assert_state_eq(
+            &state,
+            &[Complex64::new(-1.0, 0.0), Complex64::new(0.0, 0.0)],
+        );
+    }
+
+    #[test]
+    fn ry_gate_half_turn_flips_basis_state() {
+        let mut state = [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)];
+        Gate::ry(PI).apply(&mut state);
+        assert_state_eq(
+            &state,
+            &[Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0)],
+        );
+    }
+
+    #[test]
+    fn rz_gate_rotates_each_amplitude_by_half_angle() {
+        let mut state = [
+            Complex64::new(FRAC_1_SQRT_2, 0.0),
+            Complex64::new(FRAC_1_SQRT_2, 0.0),
+        ];
+        Gate::rz(PI).apply(&mut state);
+        assert_state_eq(
+            &state,
+            &[
+                Complex64::new(0.0, -FRAC_1_SQRT_2),
+                Complex64::new(0.0, FRAC_1_SQRT_2),
+            ],
+        );
+        assert_eq!(Gate::rz(PI).inverse(), Gate::rz(-PI));
+    }
 }
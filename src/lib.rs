@@ -7,6 +7,9 @@
 //!
 //! The simulator's behavior is rigorously tested against Qiskit to ensure correctness.
 //!
+//! Enable the `rayon` feature to apply gates over independent substate blocks
+//! in parallel, which pulls in `std` and is off by default for `no_std` use.
+//!
 //! ## Getting Started
 //!
 //! Here is a quick example that creates a 3-qubit GHZ state (`(|000⟩ + |111⟩)/√2`),
@@ -32,7 +35,7 @@
 //!
 //!     // 4. Start from the |000⟩ state, run the simulation, and measure.
 //!     sim.init_state(0);
-//!     sim.run();
+//!     sim.run(&mut rng);
 //!     let outcome = sim.measure(&mut rng);
 //!
 //!     // After measurement, the state will be either |000⟩ (index 0)
@@ -59,9 +62,16 @@ pub mod classical;
 
 pub mod qft;
 
+pub mod qasm;
+
+pub mod noise;
+
+pub mod shor;
+
 pub use circuit::{Circuit, QubitError};
 pub use gate::Gate;
-pub use simulator::QuantumSimulator;
+pub use noise::NoiseChannel;
+pub use simulator::{Basis, QuantumSimulator};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,160 @@
+//! Stochastic Pauli noise channels and quantum-trajectory (Monte-Carlo
+//! wavefunction) simulation.
+//!
+//! Rather than evolving a `2^n × 2^n` density matrix, [`QuantumSimulator::run_noisy`]
+//! runs a single random *trajectory*: after each gate touching a qubit with an
+//! attached [`NoiseChannel`], it samples whether an error fires and, if so,
+//! applies the corresponding Pauli (or amplitude-damping jump) directly to the
+//! state vector. Averaging many trajectories (see
+//! [`QuantumSimulator::run_noisy_trajectories`]) approximates the noisy density
+//! matrix while keeping memory at the usual `2^n` state-vector cost.
+
+use num_complex::Complex64;
+use rand::Rng;
+
+/// A single-qubit stochastic error channel.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum NoiseChannel {
+    /// With probability `p`, apply a uniformly random Pauli (X, Y, or Z)
+    Depolarizing { p: f64 },
+    /// With probability `p`, apply a Pauli-X (bit flip)
+    BitFlip { p: f64 },
+    /// With probability `p`, apply a Pauli-Z (phase flip)
+    PhaseFlip { p: f64 },
+    /// Amplitude damping (T1 decay) with decay rate `gamma`
+    AmplitudeDamping { gamma: f64 },
+}
+
+// Flips the qubit's bit: swaps the amplitude of each `0`/`1` pair.
+fn apply_x(state: &mut [Complex64], qubit: u8) {
+    let bit_mask = 1usize << qubit;
+    for i in 0..state.len() {
+        if i & bit_mask == 0 {
+            state.swap(i, i | bit_mask);
+        }
+    }
+}
+
+// Negates the amplitude of every basis state with the qubit set.
+fn apply_z(state: &mut [Complex64], qubit: u8) {
+    let bit_mask = 1usize << qubit;
+    for (i, amplitude) in state.iter_mut().enumerate() {
+        if i & bit_mask != 0 {
+            *amplitude = -*amplitude;
+        }
+    }
+}
+
+// Pauli-Y, up to an unobservable global phase: Y = i·X·Z, and since the
+// missing factor of `i` is applied uniformly to every amplitude in the state
+// vector, it has no physical effect on any measurement.
+fn apply_y(state: &mut [Complex64], qubit: u8) {
+    apply_z(state, qubit);
+    apply_x(state, qubit);
+}
+
+fn renormalize(state: &mut [Complex64]) {
+    let norm_sqr: f64 = state.iter().map(|amplitude| amplitude.norm_sqr()).sum();
+    let scale = 1.0 / norm_sqr.sqrt();
+    for amplitude in state.iter_mut() {
+        *amplitude *= scale;
+    }
+}
+
+// Quantum-trajectory amplitude damping: with probability `gamma · p1` (the
+// "jump"), the qubit decays to |0⟩ and the surviving amplitudes (those with
+// the qubit set) are moved to the qubit-clear branch and renormalized. On the
+// more likely "no jump" branch, the qubit-set amplitudes are scaled down by
+// `√(1 − gamma)` (the Kraus operator `diag(1, √(1−gamma))`) and the whole
+// state is renormalized.
+fn apply_amplitude_damping<R: Rng>(state: &mut [Complex64], qubit: u8, gamma: f64, rng: &mut R) {
+    let bit_mask = 1usize << qubit;
+
+    let p1: f64 = state
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i & bit_mask != 0)
+        .map(|(_, amplitude)| amplitude.norm_sqr())
+        .sum();
+
+    let p_jump = gamma * p1;
+
+    if rng.gen::<f64>() < p_jump {
+        for i in 0..state.len() {
+            if i & bit_mask == 0 {
+                state[i] = state[i | bit_mask];
+                state[i | bit_mask] = Complex64::ZERO;
+            }
+        }
+    } else {
+        let decay_scale = (1.0 - gamma).sqrt();
+        for (i, amplitude) in state.iter_mut().enumerate() {
+            if i & bit_mask != 0 {
+                *amplitude *= decay_scale;
+            }
+        }
+    }
+
+    renormalize(state);
+}
+
+impl NoiseChannel {
+    /// Samples whether this channel's error fires and, if so, applies it to
+    /// `qubit` within `state`.
+    pub(crate) fn sample_and_apply<R: Rng>(&self, state: &mut [Complex64], qubit: u8, rng: &mut R) {
+        match *self {
+            NoiseChannel::BitFlip { p } => {
+                if rng.gen::<f64>() < p {
+                    apply_x(state, qubit);
+                }
+            }
+            NoiseChannel::PhaseFlip { p } => {
+                if rng.gen::<f64>() < p {
+                    apply_z(state, qubit);
+                }
+            }
+            NoiseChannel::Depolarizing { p } => {
+                let u = rng.gen::<f64>();
+                if u < p / 3.0 {
+                    apply_x(state, qubit);
+                } else if u < 2.0 * p / 3.0 {
+                    apply_y(state, qubit);
+                } else if u < p {
+                    apply_z(state, qubit);
+                }
+            }
+            NoiseChannel::AmplitudeDamping { gamma } => {
+                apply_amplitude_damping(state, qubit, gamma, rng);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::*;
+    use crate::{Gate, QuantumSimulator};
+
+    // A `BitFlip` channel with `p = 1.0` fires on every trajectory, so every
+    // sampled outcome should be flipped from |0> to |1> — a sanity check that
+    // `run_noisy_trajectories` is actually sampling and applying the channel,
+    // rather than e.g. silently ignoring `noise_for_qubit`.
+    #[test]
+    fn noisy_trajectories_histogram_reflects_certain_bit_flip() {
+        let mut sim = QuantumSimulator::new(1);
+        sim.add_noise(0, NoiseChannel::BitFlip { p: 1.0 }).unwrap();
+        // A no-op gate on qubit 0 so `run_noisy` has a `Gate` step to sample
+        // the attached noise channel after.
+        sim.add_gate(Gate::phase_radians(0.0), [0]).unwrap();
+
+        sim.init_state(0);
+        let mut rng = SmallRng::seed_from_u64(0);
+        let histogram = sim.run_noisy_trajectories(50, &mut rng);
+
+        assert_eq!(histogram.get(&1).copied(), Some(50));
+        assert_eq!(histogram.get(&0), None);
+    }
+}